@@ -2,49 +2,197 @@
 extern crate test;
 
 pub use crate::observer::Observe;
-pub use failure::bail;
 use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
 
 pub mod observer;
 
 pub trait ConfigType {
     fn check_set<'a>(
+        &self,
         path: impl Iterator<Item = &'a str>,
         value: &str,
-    ) -> Result<(), failure::Error>;
+    ) -> Result<(), ConfigError>;
     fn set<'a>(
         &mut self,
         path: impl Iterator<Item = &'a str>,
         value: &str,
-    ) -> Result<(), failure::Error>;
+    ) -> Result<(), ConfigError>;
+    /// Read back the value addressed by `path`, RON-serialized
+    ///
+    /// This is the read-side counterpart to `set`: walking the same dotted path down to a leaf,
+    /// but returning its current value instead of overwriting it.
+    fn get_value<'a>(&self, path: impl Iterator<Item = &'a str>) -> Result<String, ConfigError>;
+    /// Serialize the whole value to a RON document
+    fn to_ron(&self) -> String;
+    /// Overlay `text` onto this value, leaving fields the document doesn't mention untouched
+    ///
+    /// For a leaf value this just replaces it outright. For a `config!`-generated struct, only
+    /// the fields present in `text` are merged in, field by field, so a caller can ship a
+    /// document containing just the overrides they care about.
+    fn merge_ron(&mut self, text: &str) -> Result<(), ConfigError>;
     fn get_descendants() -> &'static [&'static str] {
         &[]
     }
 }
 
+/// Everything that can go wrong while addressing or parsing a `ConfigType` by dotted path
+///
+/// `PathNotFound` and `Parse` carry the dotted path as it was found, built up one segment at a
+/// time as the error unwinds back through `set`/`check_set`/`get_value`/`merge_ron`, so a caller
+/// driving a config UI can point at exactly the field that failed.
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The path had more segments left than there were fields/indices/keys to consume
+    PathTooLong,
+    /// The path ran out before reaching a leaf
+    PathTooShort,
+    /// No field, index, or key matched this segment
+    PathNotFound { segment: String },
+    /// The addressed `Option` was `None`, so there was nothing to recurse into
+    OptionEmpty { path: String },
+    /// The value at `path` failed to parse as RON
+    Parse { path: String, source: ron::de::Error },
+    /// A condition outside the variants above, described in the message
+    Custom(String),
+}
+
+impl ConfigError {
+    /// Prepend `segment` to the dotted path carried by this error, used while unwinding out of a
+    /// nested `set`/`check_set`/`merge_ron` call so the final error names the full path.
+    ///
+    /// Public so the `config!`-generated code (which may live in a downstream crate) can call it;
+    /// not meant to be called directly.
+    #[doc(hidden)]
+    pub fn prepend(self, segment: &str) -> Self {
+        match self {
+            ConfigError::PathNotFound { segment: inner } => ConfigError::PathNotFound {
+                segment: format!("{}.{}", segment, inner),
+            },
+            ConfigError::OptionEmpty { path } => ConfigError::OptionEmpty {
+                path: if path.is_empty() {
+                    segment.to_string()
+                } else {
+                    format!("{}.{}", segment, path)
+                },
+            },
+            ConfigError::Parse { path, source } => ConfigError::Parse {
+                path: if path.is_empty() {
+                    segment.to_string()
+                } else {
+                    format!("{}.{}", segment, path)
+                },
+                source,
+            },
+            other => other,
+        }
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::PathTooLong => write!(f, "path too long"),
+            ConfigError::PathTooShort => write!(f, "path too short"),
+            ConfigError::PathNotFound { segment } => write!(f, "path not found: {}", segment),
+            ConfigError::OptionEmpty { path } => write!(f, "option is empty: {}", path),
+            ConfigError::Parse { path, source } => {
+                write!(f, "failed to parse '{}': {}", path, source)
+            }
+            ConfigError::Custom(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::Parse { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Split a RON document shaped like a struct/map into its top-level fields
+///
+/// Used by the `config!` `@make_struct` expansion to find, for each field, the fragment of the
+/// document (if any) that should be merged into it.
+#[doc(hidden)]
+pub fn ron_fields(text: &str) -> Result<HashMap<String, String>, ConfigError> {
+    let value: ron::Value =
+        ron::de::from_str(text).map_err(|source| ConfigError::Parse {
+            path: String::new(),
+            source,
+        })?;
+    match value {
+        ron::Value::Map(map) => {
+            let mut fields = HashMap::new();
+            for (key, value) in map.into_iter() {
+                if let ron::Value::String(key) = key {
+                    let text = ron::ser::to_string(&value)
+                        .map_err(|e| ConfigError::Custom(e.to_string()))?;
+                    fields.insert(key, text);
+                }
+            }
+            Ok(fields)
+        }
+        _ => Err(ConfigError::Custom(
+            "expected a RON document shaped like a struct".to_string(),
+        )),
+    }
+}
+
 macro_rules! basic_impl {
     ($ty:ty) => {
         impl $crate::ConfigType for $ty {
             fn check_set<'a>(
+                &self,
                 mut path: impl Iterator<Item = &'a str>,
                 value: &str,
-            ) -> Result<(), failure::Error> {
+            ) -> Result<(), $crate::ConfigError> {
                 if path.next().is_none() {
-                    ron::de::from_str::<Self>(value)?;
+                    ron::de::from_str::<Self>(value).map_err(|source| $crate::ConfigError::Parse {
+                        path: String::new(),
+                        source,
+                    })?;
                     Ok(())
                 } else {
-                    $crate::bail!["Path too long"]
+                    Err($crate::ConfigError::PathTooLong)
                 }
             }
             fn set<'a>(
                 &mut self,
                 mut path: impl Iterator<Item = &'a str>,
                 value: &str,
-            ) -> Result<(), failure::Error> {
+            ) -> Result<(), $crate::ConfigError> {
                 if path.next().is_some() {
-                    $crate::bail!["Path too long"];
+                    return Err($crate::ConfigError::PathTooLong);
                 }
-                *self = ron::de::from_str(value)?;
+                *self = ron::de::from_str(value).map_err(|source| $crate::ConfigError::Parse {
+                    path: String::new(),
+                    source,
+                })?;
+                Ok(())
+            }
+            fn get_value<'a>(
+                &self,
+                mut path: impl Iterator<Item = &'a str>,
+            ) -> Result<String, $crate::ConfigError> {
+                if path.next().is_some() {
+                    return Err($crate::ConfigError::PathTooLong);
+                }
+                Ok(ron::ser::to_string(self).expect("scalar values always serialize to RON"))
+            }
+            fn to_ron(&self) -> String {
+                ron::ser::to_string(self).expect("scalar values always serialize to RON")
+            }
+            fn merge_ron(&mut self, text: &str) -> Result<(), $crate::ConfigError> {
+                *self = ron::de::from_str(text).map_err(|source| $crate::ConfigError::Parse {
+                    path: String::new(),
+                    source,
+                })?;
                 Ok(())
             }
         }
@@ -67,48 +215,254 @@ basic_impl!(bool);
 
 impl ConfigType for String {
     fn check_set<'a>(
+        &self,
         mut path: impl Iterator<Item = &'a str>,
         _value: &str,
-    ) -> Result<(), failure::Error> {
+    ) -> Result<(), ConfigError> {
         if path.next().is_none() {
             Ok(())
         } else {
-            bail!["Path too long"]
+            Err(ConfigError::PathTooLong)
         }
     }
     fn set<'a>(
         &mut self,
         mut path: impl Iterator<Item = &'a str>,
         value: &str,
-    ) -> Result<(), failure::Error> {
+    ) -> Result<(), ConfigError> {
+        if path.next().is_some() {
+            return Err(ConfigError::PathTooLong);
+        }
+        *self = ron::de::from_str(value).map_err(|source| ConfigError::Parse {
+            path: String::new(),
+            source,
+        })?;
+        Ok(())
+    }
+    fn get_value<'a>(
+        &self,
+        mut path: impl Iterator<Item = &'a str>,
+    ) -> Result<String, ConfigError> {
         if path.next().is_some() {
-            bail!["Path too long"];
+            return Err(ConfigError::PathTooLong);
         }
-        *self = ron::de::from_str(value)?;
+        Ok(ron::ser::to_string(self).expect("a String always serializes to RON"))
+    }
+    fn to_ron(&self) -> String {
+        ron::ser::to_string(self).expect("a String always serializes to RON")
+    }
+    fn merge_ron(&mut self, text: &str) -> Result<(), ConfigError> {
+        *self = ron::de::from_str(text).map_err(|source| ConfigError::Parse {
+            path: String::new(),
+            source,
+        })?;
         Ok(())
     }
 }
 
-impl<X: DeserializeOwned, Y: DeserializeOwned> ConfigType for (X, Y) {
+impl<X: DeserializeOwned + Serialize, Y: DeserializeOwned + Serialize> ConfigType for (X, Y) {
     fn check_set<'a>(
+        &self,
         mut path: impl Iterator<Item = &'a str>,
         value: &str,
-    ) -> Result<(), failure::Error> {
+    ) -> Result<(), ConfigError> {
         if path.next().is_some() {
-            bail!["Path too long"];
+            return Err(ConfigError::PathTooLong);
         }
-        ron::de::from_str::<Self>(value)?;
+        ron::de::from_str::<Self>(value).map_err(|source| ConfigError::Parse {
+            path: String::new(),
+            source,
+        })?;
         Ok(())
     }
     fn set<'a>(
         &mut self,
         mut path: impl Iterator<Item = &'a str>,
         value: &str,
-    ) -> Result<(), failure::Error> {
+    ) -> Result<(), ConfigError> {
+        if path.next().is_some() {
+            return Err(ConfigError::PathTooLong);
+        }
+        *self = ron::de::from_str(value).map_err(|source| ConfigError::Parse {
+            path: String::new(),
+            source,
+        })?;
+        Ok(())
+    }
+    fn get_value<'a>(
+        &self,
+        mut path: impl Iterator<Item = &'a str>,
+    ) -> Result<String, ConfigError> {
         if path.next().is_some() {
-            bail!["Path too long"];
+            return Err(ConfigError::PathTooLong);
+        }
+        Ok(ron::ser::to_string(self).expect("a 2-tuple always serializes to RON"))
+    }
+    fn to_ron(&self) -> String {
+        ron::ser::to_string(self).expect("a 2-tuple always serializes to RON")
+    }
+    fn merge_ron(&mut self, text: &str) -> Result<(), ConfigError> {
+        *self = ron::de::from_str(text).map_err(|source| ConfigError::Parse {
+            path: String::new(),
+            source,
+        })?;
+        Ok(())
+    }
+}
+
+impl<T: ConfigType + Serialize + DeserializeOwned> ConfigType for Vec<T> {
+    fn check_set<'a>(
+        &self,
+        mut path: impl Iterator<Item = &'a str>,
+        value: &str,
+    ) -> Result<(), ConfigError> {
+        match path.next() {
+            Some(index) => match index.parse::<usize>().ok().and_then(|i| self.get(i)) {
+                Some(item) => item.check_set(path, value).map_err(|e| e.prepend(index)),
+                None => Err(ConfigError::PathNotFound {
+                    segment: index.to_string(),
+                }),
+            },
+            None => Err(ConfigError::PathTooShort),
+        }
+    }
+    fn set<'a>(
+        &mut self,
+        mut path: impl Iterator<Item = &'a str>,
+        value: &str,
+    ) -> Result<(), ConfigError> {
+        match path.next() {
+            Some(index) => {
+                let not_found = ConfigError::PathNotFound {
+                    segment: index.to_string(),
+                };
+                match index.parse::<usize>().ok().and_then(move |i| self.get_mut(i)) {
+                    Some(item) => item.set(path, value).map_err(|e| e.prepend(index)),
+                    None => Err(not_found),
+                }
+            }
+            None => Err(ConfigError::PathTooShort),
+        }
+    }
+    fn get_value<'a>(
+        &self,
+        mut path: impl Iterator<Item = &'a str>,
+    ) -> Result<String, ConfigError> {
+        match path.next() {
+            Some(index) => match index.parse::<usize>().ok().and_then(|i| self.get(i)) {
+                Some(item) => item.get_value(path).map_err(|e| e.prepend(index)),
+                None => Err(ConfigError::PathNotFound {
+                    segment: index.to_string(),
+                }),
+            },
+            None => Err(ConfigError::PathTooShort),
+        }
+    }
+    fn to_ron(&self) -> String {
+        ron::ser::to_string(self).expect("a Vec of RON-serializable values always serializes")
+    }
+    fn merge_ron(&mut self, text: &str) -> Result<(), ConfigError> {
+        *self = ron::de::from_str(text).map_err(|source| ConfigError::Parse {
+            path: String::new(),
+            source,
+        })?;
+        Ok(())
+    }
+}
+
+impl<T: ConfigType + Serialize + DeserializeOwned> ConfigType for Option<T> {
+    fn check_set<'a>(
+        &self,
+        path: impl Iterator<Item = &'a str>,
+        value: &str,
+    ) -> Result<(), ConfigError> {
+        match self {
+            Some(inner) => inner.check_set(path, value),
+            None => Err(ConfigError::OptionEmpty { path: String::new() }),
+        }
+    }
+    fn set<'a>(
+        &mut self,
+        path: impl Iterator<Item = &'a str>,
+        value: &str,
+    ) -> Result<(), ConfigError> {
+        match self {
+            Some(inner) => inner.set(path, value),
+            None => Err(ConfigError::OptionEmpty { path: String::new() }),
+        }
+    }
+    fn get_value<'a>(
+        &self,
+        path: impl Iterator<Item = &'a str>,
+    ) -> Result<String, ConfigError> {
+        match self {
+            Some(inner) => inner.get_value(path),
+            None => Err(ConfigError::OptionEmpty { path: String::new() }),
+        }
+    }
+    fn to_ron(&self) -> String {
+        ron::ser::to_string(self).expect("an Option of a RON-serializable value always serializes")
+    }
+    fn merge_ron(&mut self, text: &str) -> Result<(), ConfigError> {
+        *self = ron::de::from_str(text).map_err(|source| ConfigError::Parse {
+            path: String::new(),
+            source,
+        })?;
+        Ok(())
+    }
+}
+
+impl<T: ConfigType + Default + Serialize + DeserializeOwned> ConfigType for HashMap<String, T> {
+    fn check_set<'a>(
+        &self,
+        mut path: impl Iterator<Item = &'a str>,
+        value: &str,
+    ) -> Result<(), ConfigError> {
+        match path.next() {
+            Some(key) => match self.get(key) {
+                Some(item) => item.check_set(path, value),
+                None => T::default().check_set(path, value),
+            }
+            .map_err(|e| e.prepend(key)),
+            None => Err(ConfigError::PathTooShort),
+        }
+    }
+    fn set<'a>(
+        &mut self,
+        mut path: impl Iterator<Item = &'a str>,
+        value: &str,
+    ) -> Result<(), ConfigError> {
+        match path.next() {
+            Some(key) => self
+                .entry(key.to_string())
+                .or_default()
+                .set(path, value)
+                .map_err(|e| e.prepend(key)),
+            None => Err(ConfigError::PathTooShort),
+        }
+    }
+    fn get_value<'a>(
+        &self,
+        mut path: impl Iterator<Item = &'a str>,
+    ) -> Result<String, ConfigError> {
+        match path.next() {
+            Some(key) => match self.get(key) {
+                Some(item) => item.get_value(path).map_err(|e| e.prepend(key)),
+                None => Err(ConfigError::PathNotFound {
+                    segment: key.to_string(),
+                }),
+            },
+            None => Err(ConfigError::PathTooShort),
         }
-        *self = ron::de::from_str(value)?;
+    }
+    fn to_ron(&self) -> String {
+        ron::ser::to_string(self).expect("a HashMap of RON-serializable values always serializes")
+    }
+    fn merge_ron(&mut self, text: &str) -> Result<(), ConfigError> {
+        *self = ron::de::from_str(text).map_err(|source| ConfigError::Parse {
+            path: String::new(),
+            source,
+        })?;
         Ok(())
     }
 }
@@ -133,6 +487,14 @@ macro_rules! config {
         $crate::config!{ @make_struct $(#[$($m)*])* $name { $($t)* } }
     };
 
+    // An observable config: every directly-declared leaf is wrapped in `Observe<T, $ctx>`, and
+    // `set` routes through `Observe::compare_and_set`, so subscribers fire on change. `$ctx` is
+    // the context type passed down to subscribers (see `observer::Observe`); substructures are
+    // not supported in this form.
+    { $(#[$($m:meta)*])* observable struct $name:ident<$ctx:ident> { $($t:tt)* } } => {
+        $crate::config!{ @make_observable_struct $(#[$($m)*])* $name<$ctx> { $($t)* } }
+    };
+
     // Make struct. Ignore substructures. These are already processesd somewhere else.
     { @make_struct $(#[$($m:meta)*])* $name:ident { $($x:ident : $y:ty $({ $($t:tt)* })* $(,)* )* } } => {
         $(#[$($m)*])*
@@ -140,37 +502,173 @@ macro_rules! config {
             $(pub $x: $y),*
         }
         impl $crate::ConfigType for $name {
-            fn check_set<'a>(mut path: impl Iterator<Item = &'a str>, value: &str) -> Result<(), failure::Error> {
+            fn check_set<'a>(&self, mut path: impl Iterator<Item = &'a str>, value: &str) -> Result<(), $crate::ConfigError> {
                 match path.next() {
                     $(
                     Some(stringify![$x]) => {
-                        <$y>::check_set(path, value)
+                        self.$x.check_set(path, value).map_err(|e| e.prepend(stringify![$x]))
                     }
                     )*
-                    Some(_) => {
-                        $crate::bail!["Path is not found"]
+                    Some(item) => {
+                        Err($crate::ConfigError::PathNotFound { segment: item.to_string() })
                     }
                     None => {
-                        $crate::bail!["Path is too short"]
+                        Err($crate::ConfigError::PathTooShort)
                     }
                 }
             }
-            fn set<'a>(&mut self, mut path: impl Iterator<Item = &'a str>, value: &str) -> Result<(), failure::Error> {
-                if let Some(item) = path.next() {
-                    match item {
-                        $(
-                            stringify![$x] => {
-                                self.$x.set(path, value)
-                            }
-                        )*
-                        _ => {
-                            $crate::bail!["Path not found"]
+            fn set<'a>(&mut self, mut path: impl Iterator<Item = &'a str>, value: &str) -> Result<(), $crate::ConfigError> {
+                match path.next() {
+                    $(
+                        Some(stringify![$x]) => {
+                            self.$x.set(path, value).map_err(|e| e.prepend(stringify![$x]))
                         }
+                    )*
+                    Some(item) => {
+                        Err($crate::ConfigError::PathNotFound { segment: item.to_string() })
+                    }
+                    None => {
+                        Err($crate::ConfigError::PathTooShort)
+                    }
+                }
+            }
+            fn get_value<'a>(&self, mut path: impl Iterator<Item = &'a str>) -> Result<String, $crate::ConfigError> {
+                match path.next() {
+                    $(
+                        Some(stringify![$x]) => {
+                            self.$x.get_value(path).map_err(|e| e.prepend(stringify![$x]))
+                        }
+                    )*
+                    Some(item) => {
+                        Err($crate::ConfigError::PathNotFound { segment: item.to_string() })
+                    }
+                    None => {
+                        Err($crate::ConfigError::PathTooShort)
                     }
-                } else {
-                    $crate::bail!["Path too short"];
                 }
             }
+            fn to_ron(&self) -> String {
+                let fields: Vec<String> = vec![$(format!("{}:{}", stringify![$x], self.$x.to_ron())),*];
+                format!("({})", fields.join(","))
+            }
+            fn merge_ron(&mut self, text: &str) -> Result<(), $crate::ConfigError> {
+                #[allow(unused_variables)]
+                let fields = $crate::ron_fields(text)?;
+                $(
+                    if let Some(field_text) = fields.get(stringify![$x]) {
+                        self.$x
+                            .merge_ron(field_text)
+                            .map_err(|e| e.prepend(stringify![$x]))?;
+                    }
+                )*
+                Ok(())
+            }
+            fn get_descendants() -> &'static [&'static str] {
+                &[$(stringify![$x]),*]
+            }
+        }
+    };
+
+    // Make an observable struct: leaves are `Observe<T, $ctx>`, and path-addressed writes go
+    // through `compare_and_set` so subscribers on a field only fire when its value changes.
+    //
+    // Each leaf's own `ConfigType` impl handles the rest of the path, so a `Vec`/`Option`/
+    // `HashMap` field gets the same index/key addressing as it would outside an observable
+    // struct -- `set` just clones the current value, lets the leaf mutate the clone, and hands
+    // the result to `compare_and_set` so subscribers only fire when something actually changed.
+    { @make_observable_struct $(#[$($m:meta)*])* $name:ident<$ctx:ident> { $($x:ident : $y:ty $(,)* )* } } => {
+        $(#[$($m)*])*
+        pub struct $name<$ctx> {
+            $(pub $x: $crate::Observe<$y, $ctx>),*
+        }
+        impl<$ctx> $name<$ctx> {
+            pub fn check_set<'a>(
+                &self,
+                mut path: impl Iterator<Item = &'a str>,
+                value: &str,
+            ) -> Result<(), $crate::ConfigError> {
+                match path.next() {
+                    $(
+                    Some(stringify![$x]) => {
+                        self.$x.get().check_set(path, value).map_err(|e| e.prepend(stringify![$x]))
+                    }
+                    )*
+                    Some(item) => {
+                        Err($crate::ConfigError::PathNotFound { segment: item.to_string() })
+                    }
+                    None => {
+                        Err($crate::ConfigError::PathTooShort)
+                    }
+                }
+            }
+            pub fn set<'a>(
+                &mut self,
+                mut path: impl Iterator<Item = &'a str>,
+                value: &str,
+                ctx: &mut $ctx,
+            ) -> Result<(), $crate::ConfigError> {
+                match path.next() {
+                    $(
+                    Some(stringify![$x]) => {
+                        let mut updated = self.$x.get().clone();
+                        updated.set(path, value).map_err(|e| e.prepend(stringify![$x]))?;
+                        self.$x.compare_and_set(updated, ctx);
+                        Ok(())
+                    }
+                    )*
+                    Some(item) => {
+                        Err($crate::ConfigError::PathNotFound { segment: item.to_string() })
+                    }
+                    None => {
+                        Err($crate::ConfigError::PathTooShort)
+                    }
+                }
+            }
+        }
+        // `ConfigType` has no room for a `$ctx`, so the trait-level `set`/`merge_ron` notify
+        // subscribers with a throwaway default-constructed context; callers that need the real
+        // context threaded through (the common case for an observable config) should call the
+        // inherent `set` above directly instead of going through this impl.
+        impl<$ctx: Default> $crate::ConfigType for $name<$ctx> {
+            fn check_set<'a>(&self, path: impl Iterator<Item = &'a str>, value: &str) -> Result<(), $crate::ConfigError> {
+                self.check_set(path, value)
+            }
+            fn set<'a>(&mut self, path: impl Iterator<Item = &'a str>, value: &str) -> Result<(), $crate::ConfigError> {
+                let mut ctx = $ctx::default();
+                self.set(path, value, &mut ctx)
+            }
+            fn get_value<'a>(&self, mut path: impl Iterator<Item = &'a str>) -> Result<String, $crate::ConfigError> {
+                match path.next() {
+                    $(
+                    Some(stringify![$x]) => {
+                        self.$x.get().get_value(path).map_err(|e| e.prepend(stringify![$x]))
+                    }
+                    )*
+                    Some(item) => {
+                        Err($crate::ConfigError::PathNotFound { segment: item.to_string() })
+                    }
+                    None => {
+                        Err($crate::ConfigError::PathTooShort)
+                    }
+                }
+            }
+            fn to_ron(&self) -> String {
+                let fields: Vec<String> = vec![$(format!("{}:{}", stringify![$x], self.$x.get().to_ron())),*];
+                format!("({})", fields.join(","))
+            }
+            fn merge_ron(&mut self, text: &str) -> Result<(), $crate::ConfigError> {
+                let mut ctx = $ctx::default();
+                #[allow(unused_variables)]
+                let fields = $crate::ron_fields(text)?;
+                $(
+                    if let Some(field_text) = fields.get(stringify![$x]) {
+                        let mut updated = self.$x.get().clone();
+                        updated.merge_ron(field_text).map_err(|e| e.prepend(stringify![$x]))?;
+                        self.$x.compare_and_set(updated, &mut ctx);
+                    }
+                )*
+                Ok(())
+            }
             fn get_descendants() -> &'static [&'static str] {
                 &[$(stringify![$x]),*]
             }
@@ -254,11 +752,11 @@ mod tests {
         ];
 
         let mut x = Single::default();
-        assert![Single::check_set(once("entry"), "0.3").is_ok()];
-        assert![Single::check_set(once("entry"), "string").is_err()];
-        assert![Single::check_set("kek.nice".split('.'), "3").is_ok()];
-        assert![Single::check_set(once("kek"), "123").is_err()];
-        assert![Single::check_set("kek.nice".split('.'), "0.3").is_err()];
+        assert![x.check_set(once("entry"), "0.3").is_ok()];
+        assert![x.check_set(once("entry"), "string").is_err()];
+        assert![x.check_set("kek.nice".split('.'), "3").is_ok()];
+        assert![x.check_set(once("kek"), "123").is_err()];
+        assert![x.check_set("kek.nice".split('.'), "0.3").is_err()];
 
         x.set(once("entry"), "0.3");
         assert_eq![0.3, x.entry];
@@ -267,8 +765,210 @@ mod tests {
         assert_eq![1234, x.kek.nice];
     }
 
+    #[test]
+    fn single_entry_get_value() {
+        config![
+            #[derive(Default)]
+            struct Single {
+                entry: f32,
+                kek: TopKek {
+                    nice: i32
+                }
+            }
+        ];
+
+        let mut x = Single::default();
+        x.set(once("entry"), "0.3").unwrap();
+        assert_eq!["0.3", x.get_value(once("entry")).unwrap()];
+
+        x.set("kek.nice".split('.'), "1234").unwrap();
+        assert_eq!["1234", x.get_value("kek.nice".split('.')).unwrap()];
+
+        assert![x.get_value(once("kek")).is_err()];
+        assert![x.get_value(once("missing")).is_err()];
+    }
+
+    #[test]
+    fn vec_indexed_path() {
+        let mut x = vec![1i32, 2, 3];
+        assert![x.check_set(once("1"), "10").is_ok()];
+        assert![x.check_set(once("5"), "10").is_err()];
+        assert![x.check_set(once("oops"), "10").is_err()];
+
+        x.set(once("1"), "10").unwrap();
+        assert_eq![10, x[1]];
+        assert_eq!["10", x.get_value(once("1")).unwrap()];
+        assert![x.set(once("5"), "10").is_err()];
+    }
+
+    #[test]
+    fn option_path_passthrough() {
+        let mut some: Option<i32> = Some(1);
+        assert![some.check_set(std::iter::empty(), "2").is_ok()];
+        some.set(std::iter::empty(), "2").unwrap();
+        assert_eq![2, some.unwrap()];
+        assert_eq!["2", some.get_value(std::iter::empty()).unwrap()];
+
+        let mut none: Option<i32> = None;
+        assert![none.check_set(std::iter::empty(), "2").is_err()];
+        assert![none.set(std::iter::empty(), "2").is_err()];
+        assert![none.get_value(std::iter::empty()).is_err()];
+    }
+
+    #[test]
+    fn observable_struct_notifies_subscribers_on_change() {
+        config![
+            observable struct Settings<Ctx> {
+                volume: i32,
+            }
+        ];
+
+        fn on_volume_change(ctx: &mut i32, value: i32) {
+            *ctx = value;
+        }
+
+        let mut settings: Settings<i32> = Settings {
+            volume: Observe::new(0),
+        };
+        settings.volume.subscribe(on_volume_change);
+
+        let mut ctx = 0;
+        assert![settings.check_set(once("volume"), "10").is_ok()];
+        assert![settings.check_set(once("volume"), "not_a_number").is_err()];
+
+        settings.set(once("volume"), "10", &mut ctx).unwrap();
+        assert_eq![10, *settings.volume.get()];
+        assert_eq![10, ctx];
+
+        // Setting the same value again should not re-trigger the subscriber.
+        ctx = 0;
+        settings.set(once("volume"), "10", &mut ctx).unwrap();
+        assert_eq![0, ctx];
+    }
+
+    #[test]
+    fn observable_struct_implements_config_type() {
+        config![
+            observable struct Settings<Ctx> {
+                volume: i32,
+                tags: Vec<String>,
+            }
+        ];
+
+        let mut settings: Settings<()> = Settings {
+            volume: Observe::new(0),
+            tags: Observe::new(vec!["a".to_string(), "b".to_string()]),
+        };
+
+        // The trait-level `set` has nowhere to put a `$ctx`, so it notifies subscribers with a
+        // throwaway default-constructed one; here that's `()` itself.
+        ConfigType::set(&mut settings, once("volume"), "10").unwrap();
+        assert_eq![10, *settings.volume.get()];
+        assert_eq!["10", settings.get_value(once("volume")).unwrap()];
+
+        // A container leaf keeps its own path addressing inside an observable struct.
+        ConfigType::set(&mut settings, "tags.1".split('.'), "\"z\"").unwrap();
+        assert_eq!["z", settings.tags.get()[1]];
+        assert!(ConfigType::set(&mut settings, "tags.5".split('.'), "\"z\"").is_err());
+
+        let mut other: Settings<()> = Settings {
+            volume: Observe::new(0),
+            tags: Observe::new(vec![]),
+        };
+        other.merge_ron(&settings.to_ron()).unwrap();
+        assert_eq![*settings.volume.get(), *other.volume.get()];
+        assert_eq![settings.tags.get(), other.tags.get()];
+    }
+
+    #[test]
+    fn whole_config_round_trips_through_ron() {
+        config![
+            #[derive(Default)]
+            struct Single {
+                entry: f32,
+                kek: TopKek {
+                    nice: i32
+                }
+            }
+        ];
+
+        let mut x = Single::default();
+        x.set(once("entry"), "0.3").unwrap();
+        x.set("kek.nice".split('.'), "1234").unwrap();
+
+        let mut y = Single::default();
+        y.merge_ron(&x.to_ron()).unwrap();
+        assert_eq![x.entry, y.entry];
+        assert_eq![x.kek.nice, y.kek.nice];
+    }
+
+    #[test]
+    fn whole_config_merge_is_partial() {
+        config![
+            #[derive(Default)]
+            struct Single {
+                entry: f32,
+                kek: TopKek {
+                    nice: i32
+                }
+            }
+        ];
+
+        let mut x = Single::default();
+        x.kek.nice = 42;
+
+        x.merge_ron("(entry:0.5)").unwrap();
+        assert_eq![0.5, x.entry];
+        assert_eq![42, x.kek.nice];
+
+        assert![x.merge_ron("(entry:not_a_float)").is_err()];
+    }
+
+    #[test]
+    fn hash_map_keyed_path() {
+        let mut x: std::collections::HashMap<String, i32> = std::collections::HashMap::new();
+        assert![x.check_set(once("a"), "1").is_ok()];
+        assert![x.check_set(once("a"), "nope").is_err()];
+
+        x.set(once("a"), "1").unwrap();
+        assert_eq![1, x["a"]];
+        assert_eq!["1", x.get_value(once("a")).unwrap()];
+        assert![x.get_value(once("b")).is_err()];
+    }
+
     #[test]
     fn simple_split() {
         println!["{:?}", "lorem.ipsum.dolor".split('.').collect::<Vec<_>>()];
     }
+
+    #[test]
+    fn config_error_reports_the_full_dotted_path() {
+        config![
+            #[derive(Default)]
+            struct Single {
+                entry: f32,
+                kek: TopKek {
+                    nice: i32
+                }
+            }
+        ];
+
+        let mut x = Single::default();
+        match x.set("kek.nice".split('.'), "not_a_number") {
+            Err(ConfigError::Parse { path, .. }) => assert_eq!["kek.nice", path],
+            other => panic!["expected a Parse error, got {:?}", other],
+        }
+
+        match x.set(once("nope"), "0") {
+            Err(ConfigError::PathNotFound { segment }) => assert_eq!["nope", segment],
+            other => panic!["expected a PathNotFound error, got {:?}", other],
+        }
+
+        match x.set(once("kek"), "0") {
+            Err(ConfigError::PathTooShort) => {}
+            other => panic!["expected a PathTooShort error, got {:?}", other],
+        }
+
+        assert_eq!["path not found: nope", x.set(once("nope"), "0").unwrap_err().to_string()];
+    }
 }
@@ -1,3 +1,6 @@
+use std::future::Future;
+use std::pin::Pin;
+
 /// The observer pattern as a simple wrapper structure
 ///
 /// Store a value and a set of subscriber functions. These functions hold no state, as they are
@@ -8,6 +11,7 @@
 pub struct Observe<T, C> {
     value: T,
     subscribers: Vec<Subscriber<C, T>>,
+    async_subscribers: Vec<AsyncSubscriber<C, T>>,
 }
 
 /// A subscriber
@@ -18,12 +22,20 @@ pub struct Observe<T, C> {
 /// pointer in memory.
 type Subscriber<C, T> = fn(&mut C, T);
 
+/// An asynchronous subscriber
+///
+/// Like `Subscriber`, but returns a future instead of running to completion immediately. Use this
+/// for side effects that do I/O (persisting to disk, pushing to a network endpoint) so they don't
+/// block the setter. The same named-function-pointer caveat as `Subscriber` applies.
+type AsyncSubscriber<C, T> = for<'a> fn(&'a mut C, T) -> Pin<Box<dyn Future<Output = ()> + 'a>>;
+
 impl<T: Default, E> Default for Observe<T, E> {
     /// Construct a default observer with no subscribers
     fn default() -> Self {
         Self {
             value: T::default(),
             subscribers: vec![],
+            async_subscribers: vec![],
         }
     }
 }
@@ -68,6 +80,7 @@ impl<T: Clone, C> Observe<T, C> {
         Self {
             value,
             subscribers: vec![],
+            async_subscribers: vec![],
         }
     }
 
@@ -88,6 +101,22 @@ impl<T: Clone, C> Observe<T, C> {
         }
     }
 
+    /// Set the value to some other value and await all async subscribers in turn.
+    ///
+    /// Like any `async fn`, nothing happens until the returned future is first polled. It then
+    /// stores the value before any subscriber runs, and awaits the async subscribers one at a
+    /// time, in subscription order. It does not call the synchronous
+    /// subscribers added via `subscribe` -- the two subscriber lists are independent, so a setter
+    /// that only needs to fire synchronous side effects is never forced to pay for polling a
+    /// future.
+    pub async fn set_async(&mut self, value: T, modifier: &mut C) {
+        self.value = value.clone();
+        let async_subscribers = self.async_subscribers.clone();
+        for sub in &async_subscribers {
+            sub(modifier, value.clone()).await;
+        }
+    }
+
     /// Find a subscriber in the subscriber list
     fn find_subscriber(&self, function: Subscriber<C, T>) -> Option<usize> {
         let mut index = None;
@@ -100,6 +129,18 @@ impl<T: Clone, C> Observe<T, C> {
         index
     }
 
+    /// Find an async subscriber in the async subscriber list
+    fn find_async_subscriber(&self, function: AsyncSubscriber<C, T>) -> Option<usize> {
+        let mut index = None;
+        for (idx, sub) in self.async_subscribers.iter().enumerate() {
+            if function as *const u8 == *sub as *const u8 {
+                index = Some(idx);
+                break;
+            }
+        }
+        index
+    }
+
     /// Add a subscriber (NOTE: Should only use named functions)
     pub fn subscribe(&mut self, function: Subscriber<C, T>) {
         if self.find_subscriber(function).is_none() {
@@ -114,10 +155,29 @@ impl<T: Clone, C> Observe<T, C> {
         }
     }
 
+    /// Add an async subscriber (NOTE: Should only use named functions)
+    pub fn subscribe_async(&mut self, function: AsyncSubscriber<C, T>) {
+        if self.find_async_subscriber(function).is_none() {
+            self.async_subscribers.push(function);
+        }
+    }
+
+    /// Remove an async subscriber (NOTE: Only works with named functions)
+    pub fn unsubscribe_async(&mut self, function: AsyncSubscriber<C, T>) {
+        if let Some(idx) = self.find_async_subscriber(function) {
+            self.async_subscribers.remove(idx);
+        }
+    }
+
     /// Count the amount of subscribers
     pub fn count_subscribers(&self) -> usize {
         self.subscribers.len()
     }
+
+    /// Count the amount of async subscribers
+    pub fn count_async_subscribers(&self) -> usize {
+        self.async_subscribers.len()
+    }
 }
 
 #[cfg(test)]
@@ -125,6 +185,23 @@ mod tests {
     use super::*;
     use test::Bencher;
 
+    /// A minimal single-threaded executor, good enough for futures that never actually pend.
+    fn block_on<F: Future<Output = ()>>(future: F) {
+        use std::task::{Context, RawWaker, RawWakerVTable, Waker};
+
+        fn no_op(_: *const ()) {}
+        fn clone(_: *const ()) -> RawWaker {
+            RawWaker::new(std::ptr::null(), &VTABLE)
+        }
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, no_op, no_op, no_op);
+
+        let raw_waker = RawWaker::new(std::ptr::null(), &VTABLE);
+        let waker = unsafe { Waker::from_raw(raw_waker) };
+        let mut cx = Context::from_waker(&waker);
+        let mut future = Box::pin(future);
+        while future.as_mut().poll(&mut cx).is_pending() {}
+    }
+
     #[quickcheck_macros::quickcheck]
     fn simple_set_and_get(value: i32) {
         let mut obs = Observe::<i32, ()>::new(0);
@@ -156,6 +233,70 @@ mod tests {
         assert_eq![ctx, initial];
     }
 
+    #[quickcheck_macros::quickcheck]
+    fn simple_set_async_and_get_with_async_subscriber(value: i32) {
+        fn subscriber(ctx: &mut i64, value: i32) -> Pin<Box<dyn Future<Output = ()> + '_>> {
+            Box::pin(async move {
+                *ctx = value as i64 + 1;
+            })
+        }
+
+        let mut obs = Observe::<i32, i64>::new(0);
+        obs.subscribe_async(subscriber);
+        let mut ctx = 0;
+        block_on(obs.set_async(value, &mut ctx));
+        assert_eq![value, *obs.get()];
+        assert_eq![ctx, *obs.get() as i64 + 1];
+    }
+
+    #[quickcheck_macros::quickcheck]
+    fn set_async_does_not_call_synchronous_subscribers(value: i32) {
+        let mut obs = Observe::<i32, i64>::new(0);
+        obs.subscribe(|ctx, _| *ctx += 1);
+        let mut ctx = 0;
+        block_on(obs.set_async(value, &mut ctx));
+        assert_eq![value, *obs.get()];
+        assert_eq![0, ctx];
+    }
+
+    #[quickcheck_macros::quickcheck]
+    fn async_subscribers_run_in_subscription_order(value: i32) {
+        fn push_1(ctx: &mut Vec<i32>, _: i32) -> Pin<Box<dyn Future<Output = ()> + '_>> {
+            Box::pin(async move { ctx.push(1) })
+        }
+        fn push_2(ctx: &mut Vec<i32>, _: i32) -> Pin<Box<dyn Future<Output = ()> + '_>> {
+            Box::pin(async move { ctx.push(2) })
+        }
+
+        let mut obs = Observe::<i32, Vec<i32>>::new(0);
+        obs.subscribe_async(push_1);
+        obs.subscribe_async(push_2);
+        let mut ctx = vec![];
+        block_on(obs.set_async(value, &mut ctx));
+        assert_eq![vec![1, 2], ctx];
+    }
+
+    #[quickcheck_macros::quickcheck]
+    fn setting_same_async_subscribers_runs_only_once(mut count: u16) {
+        count = count.max(1);
+
+        fn subscriber(ctx: &mut i64, _: i32) -> Pin<Box<dyn Future<Output = ()> + '_>> {
+            Box::pin(async move { *ctx += 1 })
+        }
+
+        let mut obs = Observe::<i32, i64>::new(0);
+        for _ in 0..count {
+            obs.subscribe_async(subscriber);
+        }
+        let mut ctx = 0;
+        block_on(obs.set_async(0, &mut ctx));
+        assert_eq![1, ctx];
+        assert_eq![1, obs.count_async_subscribers()];
+
+        obs.unsubscribe_async(subscriber);
+        assert_eq![0, obs.count_async_subscribers()];
+    }
+
     #[quickcheck_macros::quickcheck]
     fn setting_same_subscribers_runs_only_once(value: i32, mut count: u16) {
         count = count.max(1);